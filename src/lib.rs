@@ -1,4 +1,7 @@
+pub mod logging;
 mod opentelemetry;
+pub mod propagation;
+pub mod resource;
 
 use std::borrow::Cow;
 use std::collections::HashMap;
@@ -20,6 +23,65 @@ use google_cloud_trace_v2::model::{
 use google_cloud_wkt::Timestamp;
 pub use opentelemetry::opentelemetry_semantic_mapping;
 
+use crate::logging::CloudLoggingTraceFields;
+use crate::resource::Resource;
+
+/// Cloud Trace's `BatchWriteSpans` request limit: at most this many spans per RPC.
+const MAX_SPANS_PER_BATCH: usize = 1000;
+/// Cloud Trace's limit on the number of attributes kept per span.
+const MAX_ATTRIBUTES_PER_SPAN: usize = 32;
+/// Cloud Trace's byte limit for span display names, event descriptions, and attribute keys.
+const MAX_NAME_BYTES: usize = 128;
+/// Cloud Trace's byte limit for attribute values.
+const MAX_ATTRIBUTE_VALUE_BYTES: usize = 256;
+
+/// Truncate `value` to at most `max_bytes` bytes (on a `char` boundary), recording how many
+/// bytes were dropped in `TruncatableString::truncated_byte_count`.
+fn truncatable_string(value: impl Into<String>, max_bytes: usize) -> TruncatableString {
+    let mut value = value.into();
+    let original_len = value.len();
+
+    if original_len <= max_bytes {
+        return TruncatableString::new().set_value(value);
+    }
+
+    let mut cut = max_bytes;
+    while cut > 0 && !value.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    value.truncate(cut);
+
+    TruncatableString::new()
+        .set_value(value)
+        .set_truncated_byte_count((original_len - cut) as i32)
+}
+
+/// Truncate `key` to at most `MAX_NAME_BYTES` bytes (on a `char` boundary). Attribute map keys
+/// are plain strings, so unlike [`truncatable_string`] there's nowhere to record a byte count.
+fn truncate_attribute_key(key: &str) -> String {
+    if key.len() <= MAX_NAME_BYTES {
+        return key.to_string();
+    }
+
+    let mut cut = MAX_NAME_BYTES;
+    while cut > 0 && !key.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    key[..cut].to_string()
+}
+
+/// Enforce the attribute value byte limit, truncating the inner string of string-typed values.
+/// Int and bool values have no byte limit and pass through unchanged.
+fn truncate_attribute_value(value: AttributeValue) -> AttributeValue {
+    match value.string_value() {
+        Some(string_value) => AttributeValue::new().set_string_value(truncatable_string(
+            string_value.value.clone(),
+            MAX_ATTRIBUTE_VALUE_BYTES,
+        )),
+        None => value,
+    }
+}
+
 fn default_tokio_runtime() -> tokio::runtime::Runtime {
     tokio::runtime::Builder::new_current_thread()
         .enable_io()
@@ -43,6 +105,30 @@ async fn default_trace_client() -> Result<TraceService, google_cloud_gax::client
         .await
 }
 
+/// Attribute keys that Cloud Trace's console renders and filters on as numbers or booleans
+/// rather than strings, by default the numeric HTTP keys already produced by
+/// [`opentelemetry_semantic_mapping`].
+fn default_typed_attribute_keys() -> &'static [&'static str] {
+    &[
+        "/http/status_code",
+        "/http/request/size",
+        "/http/response/size",
+    ]
+}
+
+fn default_attribute_value_converter(key: &str, value: &str) -> AttributeValue {
+    if default_typed_attribute_keys().contains(&key) {
+        if let Ok(int_value) = value.parse::<i64>() {
+            return AttributeValue::new().set_int_value(int_value);
+        }
+        if let Ok(bool_value) = value.parse::<bool>() {
+            return AttributeValue::new().set_bool_value(bool_value);
+        }
+    }
+
+    AttributeValue::new().set_string_value(TruncatableString::new().set_value(value))
+}
+
 fn default_span_kind_converter(
     _span_record: &SpanRecord,
     attribute_map: &mut HashMap<String, AttributeValue>,
@@ -74,6 +160,13 @@ pub struct GoogleCloudReporter {
     #[builder(default = |_, _| None)]
     stack_trace_converter:
         fn(&SpanRecord, &mut HashMap<String, AttributeValue>) -> Option<StackTrace>,
+    #[builder(default = default_attribute_value_converter)]
+    attribute_value_converter: fn(&str, &str) -> AttributeValue,
+    #[builder(default = MAX_SPANS_PER_BATCH)]
+    max_spans_per_batch: usize,
+    resource: Option<Resource>,
+    #[builder(default = false)]
+    detect_resource: bool,
 }
 
 impl<S: google_cloud_reporter_builder::IsComplete> GoogleCloudReporterBuilder<S> {
@@ -86,6 +179,14 @@ impl<S: google_cloud_reporter_builder::IsComplete> GoogleCloudReporterBuilder<S>
             reporter.trace_client = Some(default_trace_client().await?)
         }
 
+        if reporter.detect_resource && reporter.resource.is_none() {
+            reporter.resource = Some(
+                tokio::task::spawn_blocking(resource::detect_resource)
+                    .await
+                    .expect("resource detection task panicked"),
+            );
+        }
+
         Ok(reporter)
     }
 }
@@ -106,7 +207,7 @@ impl GoogleCloudReporter {
                 self.trace_project_id, span.trace_id, span_id
             ))
             .set_span_id(span_id)
-            .set_display_name(TruncatableString::new().set_value(span.name))
+            .set_display_name(truncatable_string(span.name, MAX_NAME_BYTES))
             .set_start_time(convert_unix_ns(span.begin_time_unix_ns))
             .set_end_time(convert_unix_ns(span.begin_time_unix_ns + span.duration_ns))
             .set_attributes(attributes)
@@ -134,7 +235,7 @@ impl GoogleCloudReporter {
                         &event.properties,
                         self.attribute_name_mappings.as_ref(),
                     ))
-                    .set_description(TruncatableString::new().set_value(event.name)),
+                    .set_description(truncatable_string(event.name, MAX_NAME_BYTES)),
             )
     }
 
@@ -143,31 +244,59 @@ impl GoogleCloudReporter {
         properties: &[(Cow<'static, str>, Cow<'static, str>)],
         attribute_name_mappings: Option<&HashMap<&'static str, &'static str>>,
     ) -> Attributes {
-        let mut attributes = HashMap::with_capacity(properties.len() + 1);
+        let mut attributes =
+            HashMap::with_capacity(properties.len().min(MAX_ATTRIBUTES_PER_SPAN) + 1);
 
         if let Some(service_name) = &self.service_name {
             attributes.insert(
                 "service.name".to_string(),
-                AttributeValue::new()
-                    .set_string_value(TruncatableString::new().set_value(service_name)),
+                AttributeValue::new().set_string_value(truncatable_string(
+                    service_name.clone(),
+                    MAX_ATTRIBUTE_VALUE_BYTES,
+                )),
             );
         }
 
-        attributes.extend(properties.iter().map(|(k, v)| {
+        if let Some(resource) = &self.resource {
+            for (key, value) in resource.attributes() {
+                if attributes.len() >= MAX_ATTRIBUTES_PER_SPAN {
+                    break;
+                }
+                attributes.insert(
+                    key.to_string(),
+                    AttributeValue::new()
+                        .set_string_value(truncatable_string(value, MAX_ATTRIBUTE_VALUE_BYTES)),
+                );
+            }
+        }
+
+        for (k, v) in properties {
+            if attributes.len() >= MAX_ATTRIBUTES_PER_SPAN {
+                break;
+            }
+
             let key = attribute_name_mappings
                 .as_ref()
                 .and_then(|m| m.get(k.as_ref()).copied())
                 .unwrap_or(k.as_ref());
-            (
-                key.to_string(),
-                AttributeValue::new()
-                    .set_string_value(TruncatableString::new().set_value(v.to_string())),
-            )
-        }));
+            let value = truncate_attribute_value((self.attribute_value_converter)(key, v.as_ref()));
+
+            attributes.insert(truncate_attribute_key(key), value);
+        }
 
         Attributes::new().set_attribute_map(attributes)
     }
 
+    /// Cloud Logging trace-correlation fields for `span_context`, using this reporter's
+    /// `trace_project_id`. Inject these into a `log`/`tracing` JSON layer so log entries appear
+    /// inline with their trace in the Cloud Console.
+    pub fn cloud_logging_trace_fields(
+        &self,
+        span_context: &SpanContext,
+    ) -> CloudLoggingTraceFields {
+        logging::cloud_logging_trace_fields(&self.trace_project_id, span_context)
+    }
+
     fn try_report(&self, spans: Vec<SpanRecord>) -> google_cloud_trace_v2::Result<()> {
         self.tokio_runtime.block_on(
             self.trace_client
@@ -182,13 +311,16 @@ impl GoogleCloudReporter {
 }
 
 impl Reporter for GoogleCloudReporter {
-    fn report(&mut self, spans: Vec<SpanRecord>) {
-        if spans.is_empty() {
-            return;
-        }
+    fn report(&mut self, mut spans: Vec<SpanRecord>) {
+        while !spans.is_empty() {
+            let chunk_len = spans.len().min(self.max_spans_per_batch.max(1));
+            let remaining = spans.split_off(chunk_len);
+
+            if let Err(err) = self.try_report(spans) {
+                log::error!("report to Google Cloud Trace failed: {err}");
+            }
 
-        if let Err(err) = self.try_report(spans) {
-            log::error!("report to Google Cloud Trace failed: {err}");
+            spans = remaining;
         }
     }
 }
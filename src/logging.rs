@@ -0,0 +1,82 @@
+/// Cloud Logging / Cloud Trace correlation fields.
+///
+/// Google Cloud Logging recognizes a handful of special structured-logging fields that link a
+/// log entry back to the trace and span it was emitted from, so the two show up together in the
+/// console. This mirrors how the Stackdriver exporter's `LogContext` ties log entries to traces,
+/// but works directly from a fastrace `SpanContext`.
+use fastrace::prelude::SpanContext;
+
+/// Key of the structured-logging field naming the full trace resource.
+pub const TRACE_FIELD: &str = "logging.googleapis.com/trace";
+/// Key of the structured-logging field naming the span id.
+pub const SPAN_ID_FIELD: &str = "logging.googleapis.com/spanId";
+/// Key of the structured-logging field marking whether the trace was sampled.
+pub const TRACE_SAMPLED_FIELD: &str = "logging.googleapis.com/trace_sampled";
+
+/// The Cloud Logging trace-correlation fields for a single log entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloudLoggingTraceFields {
+    /// `projects/{trace_project_id}/traces/{trace_id}`.
+    pub trace: String,
+    /// Span id of the current span, as 16 lowercase hex digits.
+    pub span_id: String,
+    /// Whether the trace is sampled.
+    pub trace_sampled: bool,
+}
+
+impl CloudLoggingTraceFields {
+    /// Build the fields for a log entry correlated with `span_context` within
+    /// `trace_project_id`.
+    pub fn new(trace_project_id: &str, span_context: &SpanContext) -> Self {
+        Self {
+            trace: format!(
+                "projects/{trace_project_id}/traces/{}",
+                span_context.trace_id
+            ),
+            span_id: span_context.span_id.to_string(),
+            trace_sampled: span_context.sampled,
+        }
+    }
+
+    /// The fields as `(key, value)` pairs, ready for injection into a `log`/`tracing` JSON
+    /// layer. `trace_sampled` keeps its `FieldValue::Bool` type so it serializes as a JSON
+    /// boolean rather than the string `"true"`/`"false"`, which is what Cloud Logging expects.
+    pub fn as_pairs(&self) -> [(&'static str, FieldValue); 3] {
+        [
+            (TRACE_FIELD, FieldValue::Str(self.trace.clone())),
+            (SPAN_ID_FIELD, FieldValue::Str(self.span_id.clone())),
+            (TRACE_SAMPLED_FIELD, FieldValue::Bool(self.trace_sampled)),
+        ]
+    }
+}
+
+/// A structured-logging field value, kept distinct from a plain `String` so that
+/// [`CloudLoggingTraceFields::as_pairs`] can preserve `trace_sampled`'s boolean type through to
+/// whatever JSON layer consumes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    Str(String),
+    Bool(bool),
+}
+
+/// Produce the Cloud Logging trace-correlation fields for the given `SpanContext`.
+///
+/// # Example
+///
+/// ```rust
+/// use fastrace::prelude::{SpanContext, SpanId, TraceId};
+/// use fastrace_google_cloud::logging::cloud_logging_trace_fields;
+///
+/// let span_context = SpanContext::new(TraceId(1), SpanId(2));
+/// let fields = cloud_logging_trace_fields("my-project", &span_context);
+/// assert_eq!(
+///     fields.trace,
+///     "projects/my-project/traces/00000000000000000000000000000001"
+/// );
+/// ```
+pub fn cloud_logging_trace_fields(
+    trace_project_id: &str,
+    span_context: &SpanContext,
+) -> CloudLoggingTraceFields {
+    CloudLoggingTraceFields::new(trace_project_id, span_context)
+}
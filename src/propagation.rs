@@ -0,0 +1,96 @@
+/// `X-Cloud-Trace-Context` propagation for fastrace `SpanContext`.
+///
+/// Google's HTTP load balancers and many GCP services stamp incoming requests with an
+/// `X-Cloud-Trace-Context` header so that traces can be joined across service boundaries. This
+/// module lets a `fastrace::prelude::SpanContext` round-trip through that header, mirroring the
+/// header-based propagation that `fastrace` already ships for W3C `traceparent`.
+///
+/// The header format is `TRACE_ID/SPAN_ID;o=OPTIONS`, where `TRACE_ID` is 32 lowercase hex
+/// characters, `SPAN_ID` is an unsigned 64-bit integer written in **decimal**, and `OPTIONS` is a
+/// bitfield whose lowest bit (`o=1`) indicates the request should be sampled.
+use fastrace::prelude::{SpanContext, SpanId, TraceId};
+use http::{HeaderMap, HeaderValue};
+
+/// The name of the header Google Cloud uses to propagate trace context.
+pub const CLOUD_TRACE_CONTEXT_HEADER: &str = "X-Cloud-Trace-Context";
+
+/// Encode a `SpanContext` as an `X-Cloud-Trace-Context` header value.
+///
+/// # Example
+///
+/// ```rust
+/// use fastrace::prelude::{SpanContext, SpanId, TraceId};
+/// use fastrace_google_cloud::propagation::encode_cloud_trace_context;
+///
+/// let span_context = SpanContext::new(TraceId(1), SpanId(2));
+/// assert_eq!(
+///     encode_cloud_trace_context(&span_context),
+///     "00000000000000000000000000000001/2;o=1"
+/// );
+/// ```
+pub fn encode_cloud_trace_context(span_context: &SpanContext) -> String {
+    format!(
+        "{:032x}/{};o={}",
+        span_context.trace_id.0,
+        span_context.span_id.0,
+        span_context.sampled as u8
+    )
+}
+
+/// Decode an `X-Cloud-Trace-Context` header value into a `SpanContext`.
+///
+/// Returns `None` if the trace id is not exactly 32 hex characters, the span id is not a valid
+/// non-zero `u64`, or the header is otherwise malformed. If the `;o=` suffix is missing, sampling
+/// is left at its default (`SpanContext::new`'s default, i.e. sampled).
+///
+/// # Example
+///
+/// ```rust
+/// use fastrace_google_cloud::propagation::decode_cloud_trace_context;
+///
+/// let span_context =
+///     decode_cloud_trace_context("00000000000000000000000000000001/2;o=1").unwrap();
+/// assert_eq!(span_context.trace_id.0, 1);
+/// assert_eq!(span_context.span_id.0, 2);
+/// assert!(span_context.sampled);
+///
+/// assert!(decode_cloud_trace_context("not-a-valid-header").is_none());
+/// ```
+pub fn decode_cloud_trace_context(header: &str) -> Option<SpanContext> {
+    let (ids, options) = match header.split_once(";o=") {
+        Some((ids, options)) => (ids, Some(options.parse::<u64>().ok()?)),
+        None => (header, None),
+    };
+
+    let (trace_id, span_id) = ids.split_once('/')?;
+
+    if trace_id.len() != 32 || !trace_id.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let trace_id = u128::from_str_radix(trace_id, 16).ok()?;
+
+    let span_id: u64 = span_id.parse().ok()?;
+    if span_id == 0 {
+        return None;
+    }
+
+    let mut span_context = SpanContext::new(TraceId(trace_id), SpanId(span_id));
+    if let Some(options) = options {
+        span_context.sampled = options & 1 == 1;
+    }
+
+    Some(span_context)
+}
+
+/// Extract a `SpanContext` from the `X-Cloud-Trace-Context` header of an `http::HeaderMap`.
+pub fn extract_from_headers(headers: &HeaderMap) -> Option<SpanContext> {
+    let value = headers.get(CLOUD_TRACE_CONTEXT_HEADER)?;
+    decode_cloud_trace_context(value.to_str().ok()?)
+}
+
+/// Inject a `SpanContext` into an `http::HeaderMap` as the `X-Cloud-Trace-Context` header.
+pub fn inject_into_headers(span_context: &SpanContext, headers: &mut HeaderMap) {
+    if let Ok(value) = HeaderValue::from_str(&encode_cloud_trace_context(span_context)) {
+        headers.insert(CLOUD_TRACE_CONTEXT_HEADER, value);
+    }
+}
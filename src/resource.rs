@@ -0,0 +1,210 @@
+/// Automatic GCP monitored-resource detection.
+///
+/// Cloud Trace and Cloud Logging group telemetry by the "monitored resource" that produced it
+/// (a GCE instance, a GKE container, a Cloud Run revision, a Cloud Function, ...).
+/// [`crate::opentelemetry_semantic_mapping`] already rewrites a handful of k8s attributes to the
+/// `g.co/r/k8s_container/*` labels Cloud Trace expects, but callers still have to set those by
+/// hand. [`detect_resource`] probes the runtime environment instead, so e.g. a Cloud Run service
+/// gets its `g.co/r/cloud_run_revision/*` labels automatically.
+use std::time::Duration;
+
+const METADATA_HOST: &str = "metadata.google.internal";
+const METADATA_FLAVOR_HEADER: &str = "Metadata-Flavor";
+const METADATA_FLAVOR_VALUE: &str = "Google";
+const METADATA_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The monitored resource hosting this process, expressed as the `g.co/r/<resource_type>/<label>`
+/// attribute pairs Cloud Trace expects on every span.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Resource {
+    attributes: Vec<(String, String)>,
+}
+
+impl Resource {
+    /// The `g.co/r/<resource_type>/<label>` attribute pairs for this resource.
+    pub fn attributes(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attributes
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    fn new(resource_type: &str, labels: Vec<(&str, String)>) -> Self {
+        Self {
+            attributes: labels
+                .into_iter()
+                .map(|(label, value)| (format!("g.co/r/{resource_type}/{label}"), value))
+                .collect(),
+        }
+    }
+
+    /// A `gce_instance` resource built from already-known values, bypassing detection.
+    pub fn gce_instance(
+        project_id: impl Into<String>,
+        instance_id: impl Into<String>,
+        zone: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            "gce_instance",
+            vec![
+                ("project_id", project_id.into()),
+                ("instance_id", instance_id.into()),
+                ("zone", zone.into()),
+            ],
+        )
+    }
+
+    /// A `k8s_container` resource (GKE) built from already-known values, bypassing detection.
+    pub fn k8s_container(
+        project_id: impl Into<String>,
+        location: impl Into<String>,
+        cluster_name: impl Into<String>,
+        namespace: impl Into<String>,
+        pod_name: impl Into<String>,
+        container_name: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            "k8s_container",
+            vec![
+                ("project_id", project_id.into()),
+                ("location", location.into()),
+                ("cluster_name", cluster_name.into()),
+                ("namespace", namespace.into()),
+                ("pod_name", pod_name.into()),
+                ("container_name", container_name.into()),
+            ],
+        )
+    }
+
+    /// A `cloud_run_revision` resource built from already-known values, bypassing detection.
+    pub fn cloud_run_revision(
+        project_id: impl Into<String>,
+        service_name: impl Into<String>,
+        revision_name: impl Into<String>,
+        location: impl Into<String>,
+        configuration_name: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            "cloud_run_revision",
+            vec![
+                ("project_id", project_id.into()),
+                ("service_name", service_name.into()),
+                ("revision_name", revision_name.into()),
+                ("location", location.into()),
+                ("configuration_name", configuration_name.into()),
+            ],
+        )
+    }
+
+    /// A `cloud_function` resource built from already-known values, bypassing detection.
+    pub fn cloud_function(
+        project_id: impl Into<String>,
+        function_name: impl Into<String>,
+        region: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            "cloud_function",
+            vec![
+                ("project_id", project_id.into()),
+                ("function_name", function_name.into()),
+                ("region", region.into()),
+            ],
+        )
+    }
+
+    /// A `generic_node` resource, used when the process isn't running on a recognized GCP
+    /// compute product.
+    pub fn generic_node(project_id: impl Into<String>, node_id: impl Into<String>) -> Self {
+        Self::new(
+            "generic_node",
+            vec![
+                ("project_id", project_id.into()),
+                ("node_id", node_id.into()),
+            ],
+        )
+    }
+}
+
+/// Probe the runtime environment and detect the GCP monitored resource hosting this process.
+///
+/// Reads the GCP metadata server for the project id, instance id, zone, and (if present) GKE
+/// cluster name, with a short timeout so non-GCP environments degrade gracefully to a
+/// `generic_node` resource keyed off the local hostname. Cloud Run and Cloud Functions are
+/// recognized from the environment variables those products set, per Google's own runtime
+/// detection conventions.
+///
+/// This performs blocking I/O and should be called once, at reporter build time, and the result
+/// cached for the lifetime of the reporter.
+pub fn detect_resource() -> Resource {
+    let project_id = metadata_value("project/project-id").unwrap_or_default();
+
+    if let Ok(service_name) = std::env::var("K_SERVICE") {
+        return Resource::cloud_run_revision(
+            project_id,
+            service_name,
+            std::env::var("K_REVISION").unwrap_or_default(),
+            metadata_location().unwrap_or_default(),
+            std::env::var("K_CONFIGURATION").unwrap_or_default(),
+        );
+    }
+
+    if let Ok(function_name) =
+        std::env::var("FUNCTION_TARGET").or_else(|_| std::env::var("FUNCTION_NAME"))
+    {
+        return Resource::cloud_function(
+            project_id,
+            function_name,
+            metadata_location().unwrap_or_default(),
+        );
+    }
+
+    let cluster_name = metadata_value("instance/attributes/cluster-name");
+    let zone = metadata_location();
+
+    if let (Some(cluster_name), Some(zone)) = (cluster_name, zone.clone()) {
+        return Resource::k8s_container(
+            project_id,
+            zone,
+            cluster_name,
+            std::env::var("NAMESPACE").unwrap_or_else(|_| "default".to_string()),
+            local_hostname(),
+            std::env::var("CONTAINER_NAME").unwrap_or_default(),
+        );
+    }
+
+    if let (Some(instance_id), Some(zone)) = (metadata_value("instance/id"), zone) {
+        return Resource::gce_instance(project_id, instance_id, zone);
+    }
+
+    Resource::generic_node(project_id, local_hostname())
+}
+
+/// The short zone or region name (e.g. `us-central1-a`), parsed out of the metadata server's
+/// fully-qualified `projects/{project}/zones/{zone}` form.
+fn metadata_location() -> Option<String> {
+    let zone = metadata_value("instance/zone")?;
+    Some(zone.rsplit('/').next().unwrap_or(&zone).to_string())
+}
+
+fn metadata_value(path: &str) -> Option<String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(METADATA_TIMEOUT)
+        .build()
+        .ok()?;
+
+    client
+        .get(format!("http://{METADATA_HOST}/computeMetadata/v1/{path}"))
+        .header(METADATA_FLAVOR_HEADER, METADATA_FLAVOR_VALUE)
+        .send()
+        .ok()?
+        .error_for_status()
+        .ok()?
+        .text()
+        .ok()
+}
+
+fn local_hostname() -> String {
+    hostname::get()
+        .ok()
+        .and_then(|hostname| hostname.into_string().ok())
+        .unwrap_or_default()
+}